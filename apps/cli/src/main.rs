@@ -7,9 +7,14 @@ use std::{
     path::PathBuf,
 };
 use tokio_stream::StreamExt;
+use vetta_core::audio::normalize;
+use vetta_core::cache::{self, CacheKeyInput};
+use vetta_core::config::PipelineConfig;
 use vetta_core::domain::Quarter as CoreQuarter;
-use vetta_core::earnings_processor::validate_media_file;
+use vetta_core::earnings_processor::{discover_media_files, validate_media_file};
+use vetta_core::fetch::{fetch_media, FetchedMedia};
 use vetta_core::stt::{LocalSttStrategy, SpeechToText, TranscribeOptions};
+use vetta_core::transcript::{self, OutputFormat as CoreOutputFormat, Segment};
 
 #[derive(Debug, Clone, ValueEnum)]
 enum CliQuarter {
@@ -30,6 +35,36 @@ impl From<CliQuarter> for CoreQuarter {
     }
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CliOutputFormat {
+    Txt,
+    Srt,
+    Vtt,
+    Json,
+}
+
+impl From<CliOutputFormat> for CoreOutputFormat {
+    fn from(cli: CliOutputFormat) -> Self {
+        match cli {
+            CliOutputFormat::Txt => CoreOutputFormat::Txt,
+            CliOutputFormat::Srt => CoreOutputFormat::Srt,
+            CliOutputFormat::Vtt => CoreOutputFormat::Vtt,
+            CliOutputFormat::Json => CoreOutputFormat::Json,
+        }
+    }
+}
+
+impl CliOutputFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            CliOutputFormat::Txt => "txt",
+            CliOutputFormat::Srt => "srt",
+            CliOutputFormat::Vtt => "vtt",
+            CliOutputFormat::Json => "json",
+        }
+    }
+}
+
 #[derive(Parser)]
 #[command(
     name = "vetta",
@@ -45,6 +80,15 @@ struct Cli {
     )]
     socket: PathBuf,
 
+    /// Explicit pipeline config file; otherwise ./vetta.toml or
+    /// $XDG_CONFIG_HOME/vetta/config.toml is used when present.
+    #[arg(long, value_name = "PATH", global = true)]
+    config: Option<PathBuf>,
+
+    /// Transcript cache directory. Defaults to $XDG_CACHE_HOME/vetta.
+    #[arg(long, value_name = "DIR", global = true)]
+    cache_dir: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Resource,
 }
@@ -60,10 +104,18 @@ enum Resource {
 
 #[derive(Subcommand)]
 enum EarningsAction {
-    #[command(about = "Process an audio/video file")]
+    #[command(about = "Process one or more audio/video files")]
     Process {
-        #[arg(short, long)]
-        file: PathBuf,
+        #[arg(short, long, value_name = "FILE", num_args = 1..)]
+        file: Vec<PathBuf>,
+
+        #[arg(long, value_name = "DIR", conflicts_with = "file")]
+        dir: Option<PathBuf>,
+
+        /// Fetch the audio from a webcast/YouTube/HLS URL instead of a local file.
+        #[arg(long, value_name = "URL", conflicts_with_all = ["file", "dir"])]
+        url: Option<String>,
+
         #[arg(short, long)]
         ticker: String,
         #[arg(short, long)]
@@ -74,9 +126,49 @@ enum EarningsAction {
         #[arg(long, value_name = "PATH")]
         out: Option<PathBuf>,
 
+        /// Overrides the configured max file size, in MB.
+        #[arg(long, value_name = "MB")]
+        max_file_size_mb: Option<u64>,
+
+        /// Overrides the configured Whisper initial prompt.
+        #[arg(long, value_name = "TEXT")]
+        prompt: Option<String>,
+
+        /// Overrides the configured transcription language.
+        #[arg(long, value_name = "LANG")]
+        language: Option<String>,
+
+        /// Enables speaker diarization, tagging each segment with a speaker id.
+        #[arg(long)]
+        diarize: bool,
+
+        /// Expected number of distinct speakers (only used with --diarize).
+        #[arg(long, value_name = "N")]
+        speakers: Option<u8>,
+
+        /// Output format for the transcript.
+        #[arg(long, value_enum, default_value = "txt")]
+        format: CliOutputFormat,
+
+        /// Skip the transcript cache and force re-transcription.
+        #[arg(long)]
+        no_cache: bool,
+
         #[arg(long)]
         print: bool,
     },
+
+    #[command(about = "Manage the cached transcripts")]
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    #[command(about = "Delete all cached transcripts")]
+    Clear,
 }
 
 #[tokio::main]
@@ -86,35 +178,175 @@ async fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
+    let pipeline_config =
+        PipelineConfig::load(cli.config.as_deref())
+            .into_diagnostic()
+            .wrap_err("Failed to load pipeline configuration")?;
+
+    let cache_dir = cli
+        .cache_dir
+        .clone()
+        .unwrap_or_else(cache::default_cache_dir);
+
     match cli.command {
         Resource::Earnings { action } => match action {
             EarningsAction::Process {
                 file,
+                dir,
+                url,
                 ticker,
                 year,
                 quarter,
                 out,
+                max_file_size_mb,
+                prompt,
+                language,
+                diarize,
+                speakers,
+                format,
+                no_cache,
                 print,
             } => {
-                run_processing_pipeline(file, ticker, year, quarter, &cli.socket, out, print)
-                    .await?;
+                let pipeline_config = apply_cli_overrides(
+                    pipeline_config,
+                    max_file_size_mb,
+                    prompt,
+                    language,
+                    diarize,
+                    speakers,
+                );
+                let (files, fetched_guard) =
+                    resolve_input_files(file, dir, url, &pipeline_config).await?;
+                let outcome = run_processing_pipeline(
+                    files,
+                    ticker,
+                    year,
+                    quarter,
+                    &cli.socket,
+                    out,
+                    print,
+                    format,
+                    &pipeline_config,
+                    &cache_dir,
+                    no_cache,
+                )
+                .await;
+                // Keep the fetched temp file alive until processing has
+                // finished with it, then let it delete on drop.
+                drop(fetched_guard);
+                outcome?;
             }
+            EarningsAction::Cache { action } => match action {
+                CacheAction::Clear => {
+                    let removed = cache::clear(&cache_dir)
+                        .into_diagnostic()
+                        .wrap_err_with(|| {
+                            format!("Failed to clear cache dir {}", cache_dir.display())
+                        })?;
+                    println!(
+                        "Cleared {removed} cached transcript(s) from {}",
+                        cache_dir.display()
+                    );
+                }
+            },
         },
     }
 
     Ok(())
 }
 
+/// Layers `--max-file-size-mb`/`--prompt`/`--language`/`--diarize`/`--speakers`
+/// over the loaded config, which is itself already layered over env vars and
+/// the config file. CLI flags are the highest-precedence source.
+fn apply_cli_overrides(
+    mut config: PipelineConfig,
+    max_file_size_mb: Option<u64>,
+    prompt: Option<String>,
+    language: Option<String>,
+    diarize: bool,
+    speakers: Option<u8>,
+) -> PipelineConfig {
+    if let Some(max_file_size_mb) = max_file_size_mb {
+        config.max_file_size_mb = max_file_size_mb;
+    }
+    if let Some(prompt) = prompt {
+        config.initial_prompt = prompt;
+    }
+    if let Some(language) = language {
+        config.language = language;
+    }
+    if diarize {
+        config.diarization = true;
+    }
+    if let Some(speakers) = speakers {
+        config.num_speakers = speakers;
+    }
+    config
+}
+
+/// Merges an explicit `--file` list with `--dir` glob discovery into a single,
+/// resolved batch of input paths. For `--url`, also returns the
+/// [`FetchedMedia`] guard owning the downloaded temp file; the caller must
+/// keep it alive until processing of that file has finished.
+async fn resolve_input_files(
+    file: Vec<PathBuf>,
+    dir: Option<PathBuf>,
+    url: Option<String>,
+    config: &PipelineConfig,
+) -> Result<(Vec<PathBuf>, Option<FetchedMedia>)> {
+    if let Some(url) = url {
+        let fetched = fetch_media(&url)
+            .await
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to fetch media from {url}"))?;
+        let path = fetched.path.clone();
+        return Ok((vec![path], Some(fetched)));
+    }
+
+    if let Some(dir) = dir {
+        let discovered = discover_media_files(&dir, config)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to scan directory {}", dir.display()))?;
+
+        if discovered.is_empty() {
+            return Err(miette::miette!(
+                "No supported media files found under {}",
+                dir.display()
+            ));
+        }
+
+        return Ok((discovered, None));
+    }
+
+    if file.is_empty() {
+        return Err(miette::miette!(
+            "Provide at least one --file, a --dir, or a --url"
+        ));
+    }
+
+    Ok((file, None))
+}
+
+struct BatchOutcome {
+    input: PathBuf,
+    result: Result<()>,
+}
+
 async fn run_processing_pipeline(
-    file: PathBuf,
+    files: Vec<PathBuf>,
     ticker: String,
     year: u16,
     quarter: CliQuarter,
     socket_path: &Path,
     out: Option<PathBuf>,
     print: bool,
+    format: CliOutputFormat,
+    config: &PipelineConfig,
+    cache_dir: &Path,
+    no_cache: bool,
 ) -> Result<()> {
     let core_quarter: CoreQuarter = quarter.into();
+    let batch = files.len() > 1;
 
     print_banner();
 
@@ -125,78 +357,220 @@ async fn run_processing_pipeline(
         core_quarter.to_string().yellow(),
         year.to_string().yellow()
     );
+    println!("   {:<10} {}", "SOCKET:".dimmed(), socket_path.display());
+    println!("   {:<10} {} file(s)", "QUEUE:".dimmed(), files.len());
+    println!();
+
+    // Connecting to the STT socket is deferred to the first actual cache
+    // miss, so a batch that hits cache on every file never requires Whisper
+    // to be up.
+    let mut stt: Option<LocalSttStrategy> = None;
+
+    let mut outcomes = Vec::with_capacity(files.len());
+
+    for (index, file) in files.into_iter().enumerate() {
+        let out_path = per_file_out_path(
+            out.as_deref(),
+            &ticker,
+            year,
+            &core_quarter,
+            index,
+            batch,
+            format,
+        );
+        let result = process_one_file(
+            &mut stt, socket_path, &file, out_path, print, format, config, cache_dir, no_cache,
+        )
+        .await;
+        outcomes.push(BatchOutcome { input: file, result });
+    }
 
-    let file_path = std::fs::canonicalize(&file)
+    print_summary(&outcomes);
+
+    if outcomes.iter().all(|o| o.result.is_err()) {
+        return Err(miette::miette!("All files in the batch failed to process"));
+    }
+
+    Ok(())
+}
+
+/// Derives the per-file output path. In single-file mode, a file is only
+/// written when `--out` is explicitly given (matching the pre-batch
+/// behavior of writing nothing at all otherwise). In batch mode each file
+/// needs its own name regardless, so one is synthesized as
+/// `{ticker}_{year}_{quarter}_{n}.{ext}`, placed inside `--out` (treated as a
+/// directory) when given. The extension follows `--format`.
+fn per_file_out_path(
+    out: Option<&Path>,
+    ticker: &str,
+    year: u16,
+    quarter: &CoreQuarter,
+    index: usize,
+    batch: bool,
+    format: CliOutputFormat,
+) -> Option<PathBuf> {
+    if !batch {
+        return out.map(Path::to_path_buf);
+    }
+
+    let name = format!(
+        "{}_{}_{}_{}.{}",
+        ticker,
+        year,
+        quarter,
+        index + 1,
+        format.extension()
+    );
+    match out {
+        Some(dir) => Some(dir.join(name)),
+        None => Some(PathBuf::from(name)),
+    }
+}
+
+async fn process_one_file(
+    stt: &mut Option<LocalSttStrategy>,
+    socket_path: &Path,
+    file: &Path,
+    out: Option<PathBuf>,
+    print: bool,
+    format: CliOutputFormat,
+    config: &PipelineConfig,
+    cache_dir: &Path,
+    no_cache: bool,
+) -> Result<()> {
+    let file_path = std::fs::canonicalize(file)
         .into_diagnostic()
         .wrap_err("Failed to resolve input path")?;
 
     println!("   {:<10} {}", "INPUT:".dimmed(), file_path.display());
-    println!("   {:<10} {}", "SOCKET:".dimmed(), socket_path.display());
-    println!();
 
-    let file_info =
-        validate_media_file(&file_path.to_string_lossy()).wrap_err("Validation phase failed")?;
+    let file_info = validate_media_file(&file_path.to_string_lossy(), config)
+        .wrap_err("Validation phase failed")?;
 
     println!("   {}", "✔ VALIDATION PASSED".green().bold());
     println!("   {:<10} {}", "Format:".dimmed(), file_info);
-    println!();
+    if let Some(duration) = file_info.duration {
+        println!("   {:<10} {}", "LENGTH:".dimmed(), format_hms(duration));
+        // Whisper throughput varies by hardware; real-time is a reasonable
+        // up-front estimate until the transcription stage has real progress.
+        println!("   {:<10} ~{}", "ETA:".dimmed(), format_hms(duration));
+    }
 
-    println!("   {}", "Processing Pipeline:".bold().blue());
     println!("   1. [✔] Validation");
-    println!("   2. [{}] Transcription (Whisper)", "RUNNING".yellow());
 
-    let stt = LocalSttStrategy::connect(socket_path.to_string_lossy())
-        .await
-        .into_diagnostic()
-        .wrap_err_with(|| {
-            format!(
-                "Failed to connect to STT service at '{}'",
-                socket_path.display()
-            )
-        })?;
-
-    let options = TranscribeOptions {
-        language: Some("en".into()),
-        initial_prompt: Some(
-            "Earnings call transcript. Financial terminology, company names, analyst questions and management responses."
-                .into(),
-        ),
-        diarization: false,
-        num_speakers: 2,
-    };
+    let normalized = normalize(&file_path).wrap_err("Normalization phase failed")?;
+    if normalized.already_conformant {
+        println!("   2. [✔] Normalization (already 16 kHz mono WAV)");
+    } else {
+        println!("   2. [✔] Normalization (transcoded to 16 kHz mono PCM)");
+    }
 
-    let mut stream = stt
-        .transcribe(&file_path.to_string_lossy(), options)
-        .await
+    let cache_key_input = CacheKeyInput {
+        language: &config.language,
+        initial_prompt: &config.initial_prompt,
+        diarization: config.diarization,
+        num_speakers: config.num_speakers,
+    };
+    let cache_key = cache::cache_key(&normalized.path, &cache_key_input)
         .into_diagnostic()
-        .wrap_err("Transcription failed")?;
-
-    let mut segment_count = 0u32;
-    let mut full = String::new();
+        .wrap_err("Failed to compute transcript cache key")?;
 
-    while let Some(result) = stream.next().await {
-        let chunk = result
+    let cached = if no_cache {
+        None
+    } else {
+        cache::load(cache_dir, &cache_key)
             .into_diagnostic()
-            .wrap_err("Error reading transcript chunk")?;
-        segment_count += 1;
+            .wrap_err("Failed to read transcript cache")?
+    };
 
-        let line = chunk.text.trim_end();
-        if !line.is_empty() {
-            full.push_str(line);
-            full.push('\n');
+    let segments = match cached {
+        Some(segments) => {
+            println!(
+                "   3. [✔] Transcription (cache hit, {} segments)",
+                segments.len()
+            );
+            segments
         }
+        None => {
+            println!("   3. [{}] Transcription (Whisper)", "RUNNING".yellow());
+
+            if stt.is_none() {
+                let connected = LocalSttStrategy::connect(socket_path.to_string_lossy())
+                    .await
+                    .into_diagnostic()
+                    .wrap_err_with(|| {
+                        format!(
+                            "Failed to connect to STT service at '{}'",
+                            socket_path.display()
+                        )
+                    })?;
+                *stt = Some(connected);
+            }
+            let stt = stt.as_ref().expect("connected above");
+
+            let options = TranscribeOptions {
+                language: Some(config.language.clone()),
+                initial_prompt: Some(config.initial_prompt.clone()),
+                diarization: config.diarization,
+                num_speakers: config.num_speakers,
+            };
+
+            let mut stream = stt
+                .transcribe(&normalized.path.to_string_lossy(), options)
+                .await
+                .into_diagnostic()
+                .wrap_err("Transcription failed")?;
+
+            let mut segments = Vec::new();
+
+            while let Some(result) = stream.next().await {
+                let chunk = result
+                    .into_diagnostic()
+                    .wrap_err("Error reading transcript chunk")?;
+
+                let text = chunk.text.trim_end().to_string();
+                if !text.is_empty() {
+                    segments.push(Segment {
+                        start: chunk.start,
+                        end: chunk.end,
+                        speaker: config.diarization.then_some(chunk.speaker),
+                        text,
+                    });
+                }
+
+                match file_info.duration {
+                    Some(duration) if duration.as_secs_f64() > 0.0 => {
+                        let last_end = segments.last().map(|s| s.end).unwrap_or(0.0);
+                        let pct = (last_end / duration.as_secs_f64() * 100.0).clamp(0.0, 100.0);
+                        print!(
+                            "\r\x1B[K   Transcribing… {pct:.0}% ({} segments)",
+                            segments.len()
+                        );
+                    }
+                    _ => print!("\r\x1B[K   Transcribing… {} segments", segments.len()),
+                }
+                io::stdout().flush().into_diagnostic()?;
+            }
 
-        print!("\r\x1B[K   Transcribing… {} segments", segment_count);
-        io::stdout().flush().into_diagnostic()?;
-    }
+            println!(
+                "\r\x1B[K   3. [✔] Transcription ({} segments)",
+                segments.len()
+            );
 
-    println!(
-        "\r\x1B[K   2. [✔] Transcription ({} segments)",
-        segment_count
-    );
+            if !no_cache {
+                cache::store(cache_dir, &cache_key, &segments)
+                    .into_diagnostic()
+                    .wrap_err("Failed to write transcript cache")?;
+            }
+
+            segments
+        }
+    };
+
+    let rendered = transcript::render(&segments, format.into());
 
     if let Some(out_path) = out {
-        std::fs::write(&out_path, full.as_bytes())
+        std::fs::write(&out_path, rendered.as_bytes())
             .into_diagnostic()
             .wrap_err_with(|| format!("Failed to write transcript to {}", out_path.display()))?;
         println!("   {:<10} {}", "OUTPUT:".dimmed(), out_path.display());
@@ -204,14 +578,46 @@ async fn run_processing_pipeline(
 
     if print {
         println!();
-        print!("{full}");
+        print!("{rendered}");
     }
 
+    println!();
     Ok(())
 }
 
+fn print_summary(outcomes: &[BatchOutcome]) {
+    let failures: Vec<_> = outcomes.iter().filter(|o| o.result.is_err()).collect();
+    let succeeded = outcomes.len() - failures.len();
+
+    println!("   {}", "Batch Summary:".bold().blue());
+    println!(
+        "   {:<10} {} succeeded, {} failed (of {})",
+        "RESULT:".dimmed(),
+        succeeded.to_string().green(),
+        failures.len().to_string().red(),
+        outcomes.len()
+    );
+
+    for failure in &failures {
+        println!(
+            "   {:<10} {}: {}",
+            "FAILED:".red(),
+            failure.input.display(),
+            failure.result.as_ref().unwrap_err()
+        );
+    }
+}
+
 fn print_banner() {
     println!();
     println!("   {}", "VETTA FINANCIAL ENGINE".bold());
     println!("   {}", "======================".dimmed());
 }
+
+fn format_hms(duration: std::time::Duration) -> String {
+    let total_secs = duration.as_secs();
+    let h = total_secs / 3600;
+    let m = (total_secs % 3600) / 60;
+    let s = total_secs % 60;
+    format!("{h:02}:{m:02}:{s:02}")
+}
@@ -0,0 +1,192 @@
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::earnings_processor::IngestError;
+
+/// Output of the normalization stage: a path to 16 kHz mono WAV audio that
+/// the STT stage can consume directly. Transcoded output is signed-16-bit
+/// PCM; a passed-through input is only checked for channel count and sample
+/// rate, not for its sample format.
+pub struct NormalizedMedia {
+    pub path: PathBuf,
+    /// `true` when `path` is the original input (already conformant WAV),
+    /// `false` when it points at a freshly transcoded temp file.
+    pub already_conformant: bool,
+    /// Owns the transcoded temp file so it's deleted once this value is
+    /// dropped; `None` when `path` is the original, caller-owned input.
+    _temp: Option<tempfile::TempPath>,
+}
+
+/// Decodes and resamples `path` to single-channel 16 kHz PCM, returning the
+/// original path unchanged if it is already a conformant WAV file. Shells
+/// out to `ffmpeg` (override the binary with `FFMPEG_BIN`).
+pub fn normalize(path: &Path) -> Result<NormalizedMedia, IngestError> {
+    if is_conformant_wav(path).map_err(IngestError::Io)? {
+        return Ok(NormalizedMedia {
+            path: path.to_path_buf(),
+            already_conformant: true,
+            _temp: None,
+        });
+    }
+
+    let ffmpeg = crate::binpath::locate_binary("ffmpeg", "FFMPEG_BIN").ok_or_else(|| {
+        IngestError::TranscodeFailed(
+            "ffmpeg binary not found on PATH (set FFMPEG_BIN to override)".to_string(),
+        )
+    })?;
+
+    let temp_path = tempfile::Builder::new()
+        .prefix("vetta-normalized-")
+        .suffix(".wav")
+        .tempfile()
+        .map_err(IngestError::Io)?
+        .into_temp_path();
+    let out_path = temp_path.to_path_buf();
+
+    let output = std::process::Command::new(&ffmpeg)
+        .arg("-y")
+        .arg("-i")
+        .arg(path)
+        .args(["-ac", "1", "-ar", "16000", "-f", "wav"])
+        .arg(&out_path)
+        .output()
+        .map_err(|e| IngestError::TranscodeFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        return Err(IngestError::TranscodeFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(NormalizedMedia {
+        path: out_path,
+        already_conformant: false,
+        _temp: Some(temp_path),
+    })
+}
+
+struct WavFmt {
+    channels: u16,
+    sample_rate: u32,
+}
+
+/// A legitimate `fmt ` chunk is 16-40 bytes; anything beyond this is either
+/// corrupt or hostile input and must be rejected before allocating for it.
+const MAX_FMT_CHUNK_SIZE: u32 = 1024;
+
+/// Returns `true` if `path` is a WAVE file whose `fmt ` chunk already
+/// declares mono, 16 kHz audio.
+fn is_conformant_wav(path: &Path) -> io::Result<bool> {
+    Ok(read_wav_fmt(path)?.is_some_and(|fmt| fmt.channels == 1 && fmt.sample_rate == 16_000))
+}
+
+fn read_wav_fmt(path: &Path) -> io::Result<Option<WavFmt>> {
+    let mut file = fs::File::open(path)?;
+
+    let mut riff_header = [0u8; 12];
+    if file.read_exact(&mut riff_header).is_err() {
+        return Ok(None);
+    }
+    if &riff_header[0..4] != b"RIFF" || &riff_header[8..12] != b"WAVE" {
+        return Ok(None);
+    }
+
+    loop {
+        let mut chunk_header = [0u8; 8];
+        if file.read_exact(&mut chunk_header).is_err() {
+            return Ok(None);
+        }
+
+        let chunk_id = &chunk_header[0..4];
+        let chunk_size = u32::from_le_bytes(chunk_header[4..8].try_into().unwrap());
+
+        if chunk_id == b"fmt " {
+            if chunk_size > MAX_FMT_CHUNK_SIZE {
+                return Ok(None);
+            }
+            let mut body = vec![0u8; chunk_size as usize];
+            file.read_exact(&mut body)?;
+            if body.len() < 8 {
+                return Ok(None);
+            }
+            return Ok(Some(WavFmt {
+                channels: u16::from_le_bytes(body[2..4].try_into().unwrap()),
+                sample_rate: u32::from_le_bytes(body[4..8].try_into().unwrap()),
+            }));
+        }
+
+        // Chunks are word-aligned; skip a padding byte for odd sizes.
+        let skip = chunk_size as i64 + (chunk_size % 2) as i64;
+        file.seek(SeekFrom::Current(skip))?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_wav(channels: u16, sample_rate: u32) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let byte_rate = sample_rate * channels as u32 * 2;
+        let block_align = channels * 2;
+
+        file.write_all(b"RIFF").unwrap();
+        file.write_all(&36u32.to_le_bytes()).unwrap();
+        file.write_all(b"WAVE").unwrap();
+        file.write_all(b"fmt ").unwrap();
+        file.write_all(&16u32.to_le_bytes()).unwrap();
+        file.write_all(&1u16.to_le_bytes()).unwrap(); // PCM
+        file.write_all(&channels.to_le_bytes()).unwrap();
+        file.write_all(&sample_rate.to_le_bytes()).unwrap();
+        file.write_all(&byte_rate.to_le_bytes()).unwrap();
+        file.write_all(&block_align.to_le_bytes()).unwrap();
+        file.write_all(&16u16.to_le_bytes()).unwrap(); // bits per sample
+        file.write_all(b"data").unwrap();
+        file.write_all(&0u32.to_le_bytes()).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    #[test]
+    fn conformant_mono_16k_wav_is_detected() {
+        let file = write_wav(1, 16_000);
+        assert!(is_conformant_wav(file.path()).unwrap());
+    }
+
+    #[test]
+    fn stereo_wav_is_not_conformant() {
+        let file = write_wav(2, 16_000);
+        assert!(!is_conformant_wav(file.path()).unwrap());
+    }
+
+    #[test]
+    fn non_wav_input_is_not_conformant() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"ID3\x03\x00\x00\x00\x00\x00\x21some_payload")
+            .unwrap();
+        assert!(!is_conformant_wav(file.path()).unwrap());
+    }
+
+    #[test]
+    fn normalize_skips_transcode_for_conformant_wav() {
+        let file = write_wav(1, 16_000);
+        let normalized = normalize(file.path()).unwrap();
+        assert!(normalized.already_conformant);
+        assert_eq!(normalized.path, file.path());
+    }
+
+    #[test]
+    fn oversized_fmt_chunk_size_is_rejected_without_huge_allocation() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"RIFF").unwrap();
+        file.write_all(&36u32.to_le_bytes()).unwrap();
+        file.write_all(b"WAVE").unwrap();
+        file.write_all(b"fmt ").unwrap();
+        file.write_all(&0xFFFF_FFFEu32.to_le_bytes()).unwrap(); // bogus oversized chunk size
+        file.flush().unwrap();
+
+        assert!(!is_conformant_wav(file.path()).unwrap());
+    }
+}
@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+
+/// Locates `name` on disk: the `env_override` environment variable is
+/// checked first (letting callers point at a non-standard install, e.g.
+/// `FFMPEG_BIN`/`FFPROBE_BIN`/`YT_DLP_BIN`), falling back to a `PATH` scan.
+pub(crate) fn locate_binary(name: &str, env_override: &str) -> Option<PathBuf> {
+    if let Some(explicit) = std::env::var_os(env_override).map(PathBuf::from) {
+        if explicit.is_file() {
+            return Some(explicit);
+        }
+    }
+
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths).find_map(|dir| {
+            let candidate = dir.join(name);
+            candidate.is_file().then_some(candidate)
+        })
+    })
+}
@@ -0,0 +1,168 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use miette::Diagnostic;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::transcript::Segment;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum CacheError {
+    #[error(transparent)]
+    #[diagnostic(code(vetta::cache::io_error))]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to (de)serialize cached transcript")]
+    #[diagnostic(code(vetta::cache::serde_error))]
+    Serde(#[from] serde_json::Error),
+}
+
+/// The parameters that affect transcription output and therefore must
+/// participate in the cache key alongside the input file's bytes.
+pub struct CacheKeyInput<'a> {
+    pub language: &'a str,
+    pub initial_prompt: &'a str,
+    pub diarization: bool,
+    pub num_speakers: u8,
+}
+
+/// Derives a content-addressed cache key from the file's bytes plus the
+/// effective `TranscribeOptions`, so a changed language/prompt/diarization
+/// setting produces a fresh cache entry rather than a stale hit. The output
+/// format is not part of the key.
+pub fn cache_key(file_path: &Path, options: &CacheKeyInput) -> Result<String, CacheError> {
+    let bytes = fs::read(file_path)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    hasher.update(options.language.as_bytes());
+    hasher.update(options.initial_prompt.as_bytes());
+    hasher.update([options.diarization as u8]);
+    hasher.update([options.num_speakers]);
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Default cache directory: `$XDG_CACHE_HOME/vetta` or `~/.cache/vetta`.
+pub fn default_cache_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join("vetta")
+}
+
+fn entry_path(cache_dir: &Path, key: &str) -> PathBuf {
+    cache_dir.join(format!("{key}.json"))
+}
+
+/// Loads the cached segments for `key`, if any.
+pub fn load(cache_dir: &Path, key: &str) -> Result<Option<Vec<Segment>>, CacheError> {
+    let path = entry_path(cache_dir, key);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(path)?;
+    Ok(Some(serde_json::from_str(&raw)?))
+}
+
+/// Persists `segments` under `key`, creating `cache_dir` if needed.
+pub fn store(cache_dir: &Path, key: &str, segments: &[Segment]) -> Result<(), CacheError> {
+    fs::create_dir_all(cache_dir)?;
+    let raw = serde_json::to_string(segments)?;
+    fs::write(entry_path(cache_dir, key), raw)?;
+    Ok(())
+}
+
+/// Removes every cached entry under `cache_dir`, returning how many were deleted.
+pub fn clear(cache_dir: &Path) -> Result<usize, CacheError> {
+    if !cache_dir.exists() {
+        return Ok(0);
+    }
+
+    let mut removed = 0;
+    for entry in fs::read_dir(cache_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            fs::remove_file(path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn options() -> CacheKeyInput<'static> {
+        CacheKeyInput {
+            language: "en",
+            initial_prompt: "house prompt",
+            diarization: false,
+            num_speakers: 2,
+        }
+    }
+
+    fn write_temp(bytes: &[u8]) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(bytes).unwrap();
+        file
+    }
+
+    #[test]
+    fn same_bytes_and_options_produce_same_key() {
+        let file = write_temp(b"some audio bytes");
+        let key_a = cache_key(file.path(), &options()).unwrap();
+        let key_b = cache_key(file.path(), &options()).unwrap();
+        assert_eq!(key_a, key_b);
+    }
+
+    #[test]
+    fn differing_options_change_the_key() {
+        let file = write_temp(b"some audio bytes");
+        let key_a = cache_key(file.path(), &options()).unwrap();
+
+        let mut other = options();
+        other.diarization = true;
+        let key_b = cache_key(file.path(), &other).unwrap();
+
+        assert_ne!(key_a, key_b);
+    }
+
+    #[test]
+    fn store_then_load_round_trips_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let segments = vec![Segment {
+            start: 0.0,
+            end: 1.0,
+            speaker: None,
+            text: "hello".to_string(),
+        }];
+
+        store(dir.path(), "abc123", &segments).unwrap();
+        let loaded = load(dir.path(), "abc123").unwrap();
+
+        assert_eq!(loaded, Some(segments));
+    }
+
+    #[test]
+    fn load_misses_when_entry_absent() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load(dir.path(), "missing").unwrap(), None);
+    }
+
+    #[test]
+    fn clear_removes_all_cached_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        store(dir.path(), "one", &[]).unwrap();
+        store(dir.path(), "two", &[]).unwrap();
+
+        let removed = clear(dir.path()).unwrap();
+
+        assert_eq!(removed, 2);
+        assert!(fs::read_dir(dir.path()).unwrap().next().is_none());
+    }
+}
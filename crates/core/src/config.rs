@@ -0,0 +1,127 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+use miette::Diagnostic;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+const DEFAULT_MAX_FILE_SIZE_MB: u64 = 500;
+const DEFAULT_ALLOWED_MIME_TYPES: [&str; 5] = [
+    "audio/mpeg",  // .mp3
+    "audio/wav",   // .wav
+    "audio/x-wav", // .wav
+    "audio/x-m4a", // .m4a
+    "video/mp4",   // .mp4
+];
+const DEFAULT_LANGUAGE: &str = "en";
+const DEFAULT_INITIAL_PROMPT: &str = "Earnings call transcript. Financial terminology, company names, analyst questions and management responses.";
+const DEFAULT_NUM_SPEAKERS: u8 = 2;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum ConfigError {
+    #[error("Failed to load pipeline configuration")]
+    #[diagnostic(
+        code(vetta::config::load_failed),
+        help("Check that vetta.toml (or the file passed via --config) is valid TOML/YAML/JSON.")
+    )]
+    Load(#[from] config::ConfigError),
+}
+
+/// Layered settings for the ingestion/transcription pipeline.
+///
+/// Precedence, highest first: explicit CLI flags (applied by the caller
+/// after loading), `VETTA_*` environment variables, `./vetta.toml` (or
+/// `$XDG_CONFIG_HOME/vetta/config.toml`), then the built-in defaults below.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PipelineConfig {
+    pub max_file_size_mb: u64,
+    pub allowed_mime_types: Vec<String>,
+    pub language: String,
+    pub initial_prompt: String,
+    pub diarization: bool,
+    pub num_speakers: u8,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size_mb: DEFAULT_MAX_FILE_SIZE_MB,
+            allowed_mime_types: DEFAULT_ALLOWED_MIME_TYPES
+                .iter()
+                .map(|mime| mime.to_string())
+                .collect(),
+            language: DEFAULT_LANGUAGE.to_string(),
+            initial_prompt: DEFAULT_INITIAL_PROMPT.to_string(),
+            diarization: false,
+            num_speakers: DEFAULT_NUM_SPEAKERS,
+        }
+    }
+}
+
+impl PipelineConfig {
+    /// Loads the effective config from `explicit_path` (`--config`) if given,
+    /// otherwise from `./vetta.toml` or `$XDG_CONFIG_HOME/vetta/config.toml`,
+    /// layered over `VETTA_*` env vars and [`PipelineConfig::default`].
+    pub fn load(explicit_path: Option<&Path>) -> Result<Self, ConfigError> {
+        let mut builder =
+            config::Config::builder().add_source(config::Config::try_from(&Self::default())?);
+
+        builder = match explicit_path {
+            Some(path) => builder.add_source(config::File::from(path.to_path_buf()).required(true)),
+            None => builder
+                .add_source(config::File::with_name("vetta").required(false))
+                .add_source(config::File::from(xdg_config_path()).required(false)),
+        };
+
+        builder = builder.add_source(
+            config::Environment::with_prefix("VETTA")
+                .separator("__")
+                .try_parsing(true),
+        );
+
+        Ok(builder.build()?.try_deserialize()?)
+    }
+}
+
+fn xdg_config_path() -> PathBuf {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join("vetta").join("config.toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn defaults_match_previous_hardcoded_values() {
+        let config = PipelineConfig::default();
+        assert_eq!(config.max_file_size_mb, DEFAULT_MAX_FILE_SIZE_MB);
+        assert_eq!(config.allowed_mime_types, DEFAULT_ALLOWED_MIME_TYPES);
+        assert_eq!(config.language, "en");
+        assert!(!config.diarization);
+        assert_eq!(config.num_speakers, 2);
+    }
+
+    #[test]
+    fn load_with_no_explicit_path_falls_back_to_defaults() {
+        let config = PipelineConfig::load(None).unwrap();
+        assert_eq!(config, PipelineConfig::default());
+    }
+
+    #[test]
+    fn load_applies_explicit_file_overrides() {
+        let mut file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+        writeln!(file, "max_file_size_mb = 1024").unwrap();
+        writeln!(file, "initial_prompt = \"house prompt\"").unwrap();
+
+        let config = PipelineConfig::load(Some(file.path())).unwrap();
+
+        assert_eq!(config.max_file_size_mb, 1024);
+        assert_eq!(config.initial_prompt, "house prompt");
+    }
+}
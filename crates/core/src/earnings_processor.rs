@@ -1,16 +1,12 @@
 use miette::Diagnostic;
+use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use thiserror::Error;
 
-const MAX_FILE_SIZE_MB: u64 = 500;
-const ALLOWED_MIME_TYPES: [&str; 5] = [
-    "audio/mpeg",  // .mp3
-    "audio/wav",   // .wav
-    "audio/x-wav", // .wav
-    "audio/x-m4a", // .m4a
-    "video/mp4",   // .mp4
-];
+use crate::config::PipelineConfig;
 
 #[derive(Error, Debug, Diagnostic)]
 pub enum IngestError {
@@ -53,12 +49,85 @@ pub enum IngestError {
     )]
     UnknownType,
 
+    #[error("Could not transcode media to 16 kHz mono PCM: {0}")]
+    #[diagnostic(
+        code(vetta::ingest::transcode_failed),
+        help("Make sure ffmpeg is installed and on PATH, or set FFMPEG_BIN to its location.")
+    )]
+    TranscodeFailed(String),
+
+    #[error("Failed to fetch media: {0}")]
+    #[diagnostic(
+        code(vetta::ingest::fetch_failed),
+        help(
+            "Check the URL is reachable and that yt-dlp is installed for non-HLS sources (YT_DLP_BIN to override)."
+        )
+    )]
+    FetchFailed(String),
+
     #[error(transparent)]
     #[diagnostic(code(vetta::io::error))]
     Io(#[from] std::io::Error),
 }
 
-pub fn validate_media_file(path_str: &str) -> Result<String, IngestError> {
+/// Recursively discovers media files under `dir` whose magic bytes match
+/// `config.allowed_mime_types`, for `--dir`/glob-style batch ingestion.
+pub fn discover_media_files(dir: &Path, config: &PipelineConfig) -> Result<Vec<PathBuf>, IngestError> {
+    let mut found = Vec::new();
+    collect_media_files(dir, config, &mut found)?;
+    found.sort();
+    Ok(found)
+}
+
+fn collect_media_files(
+    dir: &Path,
+    config: &PipelineConfig,
+    out: &mut Vec<PathBuf>,
+) -> Result<(), IngestError> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_media_files(&path, config, out)?;
+        } else if is_supported_media(&path, config) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn is_supported_media(path: &Path, config: &PipelineConfig) -> bool {
+    infer::get_from_path(path)
+        .ok()
+        .flatten()
+        .is_some_and(|kind| is_allowed_mime(config, kind.mime_type()))
+}
+
+fn is_allowed_mime(config: &PipelineConfig, mime_type: &str) -> bool {
+    config.allowed_mime_types.iter().any(|m| m == mime_type)
+}
+
+/// Structured result of [`validate_media_file`]. `Display` reproduces the
+/// original `"{mime} ({size}MB)"` summary so existing callers/tests keep
+/// working; the richer fields are best-effort (`None` when no prober could
+/// read them).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaInfo {
+    pub mime: String,
+    pub size_mb: u64,
+    pub duration: Option<Duration>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u16>,
+    pub codec: Option<String>,
+    pub embedded_title: Option<String>,
+}
+
+impl fmt::Display for MediaInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}MB)", self.mime, self.size_mb)
+    }
+}
+
+pub fn validate_media_file(path_str: &str, config: &PipelineConfig) -> Result<MediaInfo, IngestError> {
     let path = Path::new(path_str);
 
     if !path.exists() {
@@ -71,9 +140,9 @@ pub fn validate_media_file(path_str: &str) -> Result<String, IngestError> {
     }
 
     let size_mb = metadata.len() / (1024 * 1024);
-    if size_mb > MAX_FILE_SIZE_MB {
+    if size_mb > config.max_file_size_mb {
         return Err(IngestError::FileTooLarge {
-            limit: MAX_FILE_SIZE_MB,
+            limit: config.max_file_size_mb,
             got: size_mb,
         });
     }
@@ -82,11 +151,167 @@ pub fn validate_media_file(path_str: &str) -> Result<String, IngestError> {
         .map_err(IngestError::Io)?
         .ok_or(IngestError::UnknownType)?;
 
-    if !ALLOWED_MIME_TYPES.contains(&kind.mime_type()) {
+    if !is_allowed_mime(config, kind.mime_type()) {
         return Err(IngestError::InvalidFormat(kind.mime_type().to_string()));
     }
 
-    Ok(format!("{} ({}MB)", kind.mime_type(), size_mb))
+    let probe = probe_media(path, kind.mime_type()).unwrap_or_default();
+
+    Ok(MediaInfo {
+        mime: kind.mime_type().to_string(),
+        size_mb,
+        duration: probe.duration,
+        sample_rate: probe.sample_rate,
+        channels: probe.channels,
+        codec: probe.codec,
+        embedded_title: probe.embedded_title,
+    })
+}
+
+#[derive(Debug, Clone, Default)]
+struct ProbeResult {
+    duration: Option<Duration>,
+    sample_rate: Option<u32>,
+    channels: Option<u16>,
+    codec: Option<String>,
+    embedded_title: Option<String>,
+}
+
+/// A legitimate `fmt ` chunk is 16-40 bytes; anything beyond this is either
+/// corrupt or hostile input and must be rejected before allocating for it.
+const MAX_FMT_CHUNK_SIZE: u32 = 1024;
+
+/// Probes container/codec metadata via `ffprobe` when available, falling
+/// back to a native WAV header read so validation still reports something
+/// useful without external tools installed.
+fn probe_media(path: &Path, mime: &str) -> Option<ProbeResult> {
+    if let Some(probe) = probe_with_ffprobe(path) {
+        return Some(probe);
+    }
+
+    if mime == "audio/wav" || mime == "audio/x-wav" {
+        return probe_wav_header(path).ok().flatten();
+    }
+
+    None
+}
+
+fn probe_with_ffprobe(path: &Path) -> Option<ProbeResult> {
+    let ffprobe = crate::binpath::locate_binary("ffprobe", "FFPROBE_BIN")?;
+
+    let output = std::process::Command::new(ffprobe)
+        .args(["-v", "quiet", "-print_format", "json", "-show_format", "-show_streams"])
+        .arg(path)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    let format = json.get("format");
+
+    let duration = format
+        .and_then(|f| f.get("duration"))
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse::<f64>().ok())
+        .map(Duration::from_secs_f64);
+
+    let embedded_title = format
+        .and_then(|f| f.get("tags"))
+        .and_then(|tags| tags.get("title"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    let audio_stream = json.get("streams").and_then(|s| s.as_array()).and_then(|streams| {
+        streams
+            .iter()
+            .find(|stream| stream.get("codec_type").and_then(|v| v.as_str()) == Some("audio"))
+    });
+
+    let sample_rate = audio_stream
+        .and_then(|s| s.get("sample_rate"))
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse::<u32>().ok());
+    let channels = audio_stream
+        .and_then(|s| s.get("channels"))
+        .and_then(|v| v.as_u64())
+        .map(|c| c as u16);
+    let codec = audio_stream
+        .and_then(|s| s.get("codec_name"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    Some(ProbeResult {
+        duration,
+        sample_rate,
+        channels,
+        codec,
+        embedded_title,
+    })
+}
+
+/// Reads just enough of a WAV's `fmt `/`data` chunks to derive sample rate,
+/// channel count and duration (`data` byte length / byte rate).
+fn probe_wav_header(path: &Path) -> std::io::Result<Option<ProbeResult>> {
+    let mut file = fs::File::open(path)?;
+
+    let mut riff_header = [0u8; 12];
+    if file.read_exact(&mut riff_header).is_err() {
+        return Ok(None);
+    }
+    if &riff_header[0..4] != b"RIFF" || &riff_header[8..12] != b"WAVE" {
+        return Ok(None);
+    }
+
+    let mut channels = None;
+    let mut sample_rate = None;
+    let mut byte_rate = None;
+    let mut data_size = None;
+
+    loop {
+        let mut chunk_header = [0u8; 8];
+        if file.read_exact(&mut chunk_header).is_err() {
+            break;
+        }
+        let chunk_id = &chunk_header[0..4];
+        let chunk_size = u32::from_le_bytes(chunk_header[4..8].try_into().unwrap());
+
+        if chunk_id == b"fmt " {
+            if chunk_size > MAX_FMT_CHUNK_SIZE {
+                return Ok(None);
+            }
+            let mut body = vec![0u8; chunk_size as usize];
+            file.read_exact(&mut body)?;
+            if body.len() >= 12 {
+                channels = Some(u16::from_le_bytes(body[2..4].try_into().unwrap()));
+                sample_rate = Some(u32::from_le_bytes(body[4..8].try_into().unwrap()));
+                byte_rate = Some(u32::from_le_bytes(body[8..12].try_into().unwrap()));
+            }
+        } else if chunk_id == b"data" {
+            data_size = Some(chunk_size);
+            break;
+        } else {
+            let skip = chunk_size as i64 + (chunk_size % 2) as i64;
+            file.seek(SeekFrom::Current(skip))?;
+        }
+    }
+
+    let duration = match (data_size, byte_rate) {
+        (Some(data_size), Some(byte_rate)) if byte_rate > 0 => {
+            Some(Duration::from_secs_f64(data_size as f64 / byte_rate as f64))
+        }
+        _ => None,
+    };
+
+    Ok(Some(ProbeResult {
+        duration,
+        sample_rate,
+        channels,
+        codec: Some("pcm_s16le".to_string()),
+        embedded_title: None,
+    }))
 }
 
 #[cfg(test)]
@@ -101,8 +326,8 @@ mod tests {
         file
     }
 
-    fn validate_path(path: &Path) -> Result<String, IngestError> {
-        validate_media_file(path.to_str().expect("utf-8 temp path"))
+    fn validate_path(path: &Path) -> Result<MediaInfo, IngestError> {
+        validate_media_file(path.to_str().expect("utf-8 temp path"), &PipelineConfig::default())
     }
 
     #[test]
@@ -110,7 +335,7 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("non_existent_file.mp3");
         let path_str = path.to_str().unwrap();
-        let err = validate_media_file(path_str).unwrap_err();
+        let err = validate_media_file(path_str, &PipelineConfig::default()).unwrap_err();
         assert!(matches!(err, IngestError::FileNotFound(p) if p == path_str));
     }
 
@@ -123,18 +348,19 @@ mod tests {
 
     #[test]
     fn file_too_large_reports_limit_and_got() {
+        let limit = PipelineConfig::default().max_file_size_mb;
         let mut file = NamedTempFile::new().unwrap();
 
         file.as_file_mut()
-            .set_len((MAX_FILE_SIZE_MB + 1) * 1024 * 1024)
+            .set_len((limit + 1) * 1024 * 1024)
             .unwrap();
 
         let err = validate_path(file.path()).unwrap_err();
 
         assert!(matches!(
             err,
-            IngestError::FileTooLarge { limit, got }
-                if limit == MAX_FILE_SIZE_MB && got == MAX_FILE_SIZE_MB + 1
+            IngestError::FileTooLarge { limit: got_limit, got }
+                if got_limit == limit && got == limit + 1
         ));
     }
 
@@ -170,10 +396,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn discover_media_files_finds_supported_types_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("call.mp3"),
+            b"ID3\x03\x00\x00\x00\x00\x00\x21some_payload",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.pdf"), b"%PDF-1.4\n...payload...").unwrap();
+
+        let nested = dir.path().join("q1");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(
+            nested.join("call.wav"),
+            b"RIFF\x24\x00\x00\x00WAVEfmt ",
+        )
+        .unwrap();
+
+        let found = discover_media_files(dir.path(), &PipelineConfig::default()).unwrap();
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().any(|p| p.ends_with("call.mp3")));
+        assert!(found.iter().any(|p| p.ends_with("q1/call.wav")));
+    }
+
     #[test]
     fn ok_message_includes_mime_and_size_suffix() {
         let file = write_temp(b"ID3\x03\x00\x00\x00\x00\x00\x21some_payload");
-        let msg = validate_path(file.path()).unwrap();
+        let info = validate_path(file.path()).unwrap();
+        let msg = info.to_string();
 
         assert!(
             msg.contains("audio/mpeg"),
@@ -184,4 +435,52 @@ mod tests {
             "expected message to end with 'MB)', got: {msg}"
         );
     }
+
+    #[test]
+    fn wav_header_yields_duration_sample_rate_and_channels() {
+        let mut file = NamedTempFile::new().unwrap();
+        let sample_rate = 16_000u32;
+        let channels = 1u16;
+        let byte_rate = sample_rate * channels as u32 * 2;
+        let num_samples = sample_rate; // 1 second of audio
+        let data_size = num_samples * channels as u32 * 2;
+
+        file.write_all(b"RIFF").unwrap();
+        file.write_all(&36u32.to_le_bytes()).unwrap();
+        file.write_all(b"WAVE").unwrap();
+        file.write_all(b"fmt ").unwrap();
+        file.write_all(&16u32.to_le_bytes()).unwrap();
+        file.write_all(&1u16.to_le_bytes()).unwrap();
+        file.write_all(&channels.to_le_bytes()).unwrap();
+        file.write_all(&sample_rate.to_le_bytes()).unwrap();
+        file.write_all(&byte_rate.to_le_bytes()).unwrap();
+        file.write_all(&(channels * 2).to_le_bytes()).unwrap();
+        file.write_all(&16u16.to_le_bytes()).unwrap();
+        file.write_all(b"data").unwrap();
+        file.write_all(&data_size.to_le_bytes()).unwrap();
+        file.write_all(&vec![0u8; data_size as usize]).unwrap();
+        file.flush().unwrap();
+
+        let info = validate_path(file.path()).unwrap();
+
+        assert_eq!(info.sample_rate, Some(16_000));
+        assert_eq!(info.channels, Some(1));
+        assert_eq!(info.duration, Some(std::time::Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn oversized_fmt_chunk_in_wav_is_ignored_without_huge_allocation() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"RIFF").unwrap();
+        file.write_all(&36u32.to_le_bytes()).unwrap();
+        file.write_all(b"WAVE").unwrap();
+        file.write_all(b"fmt ").unwrap();
+        file.write_all(&0xFFFF_FFFEu32.to_le_bytes()).unwrap(); // bogus oversized chunk size
+        file.flush().unwrap();
+
+        let info = validate_path(file.path()).unwrap();
+
+        assert_eq!(info.sample_rate, None);
+        assert_eq!(info.duration, None);
+    }
 }
@@ -0,0 +1,278 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::earnings_processor::IngestError;
+
+/// A downloaded (and, for HLS, remuxed) media file. Owns the backing temp
+/// file so it's deleted once the caller is done with it; see
+/// [`FetchedMedia::path`].
+pub struct FetchedMedia {
+    pub path: PathBuf,
+    _temp: tempfile::TempPath,
+}
+
+/// Downloads media referenced by `url` into a local temp file so it can be
+/// handed to [`crate::earnings_processor::validate_media_file`] like any
+/// other input. Vendor webcast/YouTube links go through `yt-dlp`; raw HLS
+/// (`.m3u8`) playlists are resolved and downloaded natively, then remuxed
+/// with `ffmpeg`.
+pub async fn fetch_media(url: &str) -> Result<FetchedMedia, IngestError> {
+    if is_hls_playlist_url(url) {
+        fetch_hls_playlist(url).await
+    } else {
+        fetch_via_external_tool(url)
+    }
+}
+
+fn is_hls_playlist_url(url: &str) -> bool {
+    url.split(['?', '#'])
+        .next()
+        .unwrap_or(url)
+        .ends_with(".m3u8")
+}
+
+fn fetch_via_external_tool(url: &str) -> Result<FetchedMedia, IngestError> {
+    let yt_dlp = crate::binpath::locate_binary("yt-dlp", "YT_DLP_BIN").ok_or_else(|| {
+        IngestError::FetchFailed(
+            "yt-dlp binary not found on PATH (set YT_DLP_BIN to override)".to_string(),
+        )
+    })?;
+
+    let out_temp = new_temp_path("vetta-fetch-", ".media")?;
+    let out_path = out_temp.to_path_buf();
+
+    let output = std::process::Command::new(&yt_dlp)
+        .arg("--no-playlist")
+        .arg("-o")
+        .arg(&out_path)
+        .arg(url)
+        .output()
+        .map_err(|e| IngestError::FetchFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        return Err(IngestError::FetchFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(FetchedMedia {
+        path: out_path,
+        _temp: out_temp,
+    })
+}
+
+async fn fetch_hls_playlist(url: &str) -> Result<FetchedMedia, IngestError> {
+    let master_text = http_get_text(url).await?;
+    let media_playlist_url = select_media_playlist(url, &master_text);
+
+    let media_text = if media_playlist_url == url {
+        master_text
+    } else {
+        http_get_text(&media_playlist_url).await?
+    };
+
+    let segment_urls = parse_segment_uris(&media_playlist_url, &media_text);
+    if segment_urls.is_empty() {
+        return Err(IngestError::FetchFailed(
+            "HLS playlist contained no segments".to_string(),
+        ));
+    }
+
+    let mut segment_temps = Vec::with_capacity(segment_urls.len());
+    for segment_url in segment_urls {
+        let bytes = http_get_bytes(&segment_url).await?;
+        let segment_temp = new_temp_path("vetta-hls-segment-", ".ts")?;
+        fs::write(&segment_temp, &bytes).map_err(IngestError::Io)?;
+        segment_temps.push(segment_temp);
+    }
+
+    remux_segments(&segment_temps)
+}
+
+/// Concatenates downloaded `.ts` segments via `ffmpeg`'s `concat:` protocol
+/// and remuxes them to `.m4a`. Raw MPEG-TS bytes aren't a container `infer`
+/// (or `PipelineConfig::allowed_mime_types`) recognizes, so handing them
+/// straight to `validate_media_file` would always fail with `UnknownType`.
+fn remux_segments(segment_paths: &[tempfile::TempPath]) -> Result<FetchedMedia, IngestError> {
+    let ffmpeg = crate::binpath::locate_binary("ffmpeg", "FFMPEG_BIN").ok_or_else(|| {
+        IngestError::FetchFailed(
+            "ffmpeg binary not found on PATH (set FFMPEG_BIN to override)".to_string(),
+        )
+    })?;
+
+    let concat_input = format!(
+        "concat:{}",
+        segment_paths
+            .iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("|")
+    );
+
+    let out_temp = new_temp_path("vetta-hls-", ".m4a")?;
+    let out_path = out_temp.to_path_buf();
+
+    let output = std::process::Command::new(&ffmpeg)
+        .arg("-y")
+        .arg("-i")
+        .arg(&concat_input)
+        .args(["-vn", "-acodec", "copy"])
+        .arg(&out_path)
+        .output()
+        .map_err(|e| IngestError::FetchFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        return Err(IngestError::FetchFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(FetchedMedia {
+        path: out_path,
+        _temp: out_temp,
+    })
+}
+
+/// Picks a rendition from an `#EXT-X-STREAM-INF` master playlist: the
+/// lowest-`BANDWIDTH` audio-only variant (no `RESOLUTION` attribute) if one
+/// exists, otherwise the lowest-`BANDWIDTH` variant. Returns `base_url` unchanged if `text` is
+/// already a media playlist (no `#EXT-X-STREAM-INF` lines).
+fn select_media_playlist(base_url: &str, text: &str) -> String {
+    let mut best: Option<(bool, u64, &str)> = None;
+    let mut lines = text.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if !line.starts_with("#EXT-X-STREAM-INF:") {
+            continue;
+        }
+        let Some(&uri) = lines.peek() else { continue };
+        if uri.starts_with('#') {
+            continue;
+        }
+
+        let attrs = &line["#EXT-X-STREAM-INF:".len()..];
+        let audio_only = !attrs.contains("RESOLUTION=");
+        let bandwidth = attrs
+            .split(',')
+            .find_map(|attr| attr.strip_prefix("BANDWIDTH="))
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(u64::MAX);
+
+        let is_better = match best {
+            None => true,
+            Some((best_audio_only, best_bandwidth, _)) => {
+                (audio_only && !best_audio_only) || (audio_only == best_audio_only && bandwidth < best_bandwidth)
+            }
+        };
+        if is_better {
+            best = Some((audio_only, bandwidth, uri));
+        }
+    }
+
+    match best {
+        Some((_, _, uri)) => resolve_url(base_url, uri),
+        None => base_url.to_string(),
+    }
+}
+
+fn parse_segment_uris(base_url: &str, text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|uri| resolve_url(base_url, uri))
+        .collect()
+}
+
+/// Resolves `target` (an `#EXTINF` segment URI or variant URI) against the
+/// playlist's own URL per RFC 3986, covering absolute URLs, root-relative
+/// paths (`/live/seg0.ts`) and playlist-relative paths alike.
+fn resolve_url(base_url: &str, target: &str) -> String {
+    match url::Url::parse(base_url).and_then(|base| base.join(target)) {
+        Ok(resolved) => resolved.to_string(),
+        Err(_) => target.to_string(),
+    }
+}
+
+/// Issues `url` on the async `reqwest` client. `fetch_media` runs inside the
+/// CLI's Tokio runtime, and `reqwest::blocking` panics unconditionally when
+/// called from within one, so HLS fetches must stay on the async client.
+async fn http_get_text(url: &str) -> Result<String, IngestError> {
+    let response = reqwest::get(url)
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| IngestError::FetchFailed(e.to_string()))?;
+    response
+        .text()
+        .await
+        .map_err(|e| IngestError::FetchFailed(e.to_string()))
+}
+
+async fn http_get_bytes(url: &str) -> Result<Vec<u8>, IngestError> {
+    let response = reqwest::get(url)
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| IngestError::FetchFailed(e.to_string()))?;
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| IngestError::FetchFailed(e.to_string()))?;
+    Ok(bytes.to_vec())
+}
+
+fn new_temp_path(prefix: &str, suffix: &str) -> Result<tempfile::TempPath, IngestError> {
+    Ok(tempfile::Builder::new()
+        .prefix(prefix)
+        .suffix(suffix)
+        .tempfile()
+        .map_err(IngestError::Io)?
+        .into_temp_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_audio_only_rendition_when_present() {
+        let playlist = "#EXTM3U\n\
+#EXT-X-STREAM-INF:BANDWIDTH=128000,CODECS=\"mp4a.40.2\"\n\
+audio/index.m3u8\n\
+#EXT-X-STREAM-INF:BANDWIDTH=2000000,RESOLUTION=1280x720\n\
+video720/index.m3u8\n";
+
+        let picked = select_media_playlist("https://cdn.example.com/live/master.m3u8", playlist);
+        assert_eq!(picked, "https://cdn.example.com/live/audio/index.m3u8");
+    }
+
+    #[test]
+    fn selects_lowest_bandwidth_when_no_audio_only_rendition() {
+        let playlist = "#EXTM3U\n\
+#EXT-X-STREAM-INF:BANDWIDTH=2000000,RESOLUTION=1280x720\n\
+video720/index.m3u8\n\
+#EXT-X-STREAM-INF:BANDWIDTH=800000,RESOLUTION=640x360\n\
+video360/index.m3u8\n";
+
+        let picked = select_media_playlist("https://cdn.example.com/live/master.m3u8", playlist);
+        assert_eq!(picked, "https://cdn.example.com/live/video360/index.m3u8");
+    }
+
+    #[test]
+    fn media_playlist_without_stream_inf_is_returned_unchanged() {
+        let playlist = "#EXTM3U\n#EXTINF:10.0,\nseg0.ts\n#EXTINF:10.0,\nseg1.ts\n";
+        let url = "https://cdn.example.com/live/audio/index.m3u8";
+        assert_eq!(select_media_playlist(url, playlist), url);
+    }
+
+    #[test]
+    fn parses_and_resolves_relative_segment_uris() {
+        let playlist = "#EXTM3U\n#EXTINF:10.0,\nseg0.ts\n#EXTINF:10.0,\nseg1.ts\n";
+        let segments = parse_segment_uris("https://cdn.example.com/live/audio/index.m3u8", playlist);
+        assert_eq!(
+            segments,
+            vec![
+                "https://cdn.example.com/live/audio/seg0.ts",
+                "https://cdn.example.com/live/audio/seg1.ts",
+            ]
+        );
+    }
+}
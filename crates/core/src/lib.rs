@@ -0,0 +1,9 @@
+pub mod audio;
+mod binpath;
+pub mod cache;
+pub mod config;
+pub mod domain;
+pub mod earnings_processor;
+pub mod fetch;
+pub mod stt;
+pub mod transcript;
@@ -0,0 +1,118 @@
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+use miette::Diagnostic;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+use tokio_stream::wrappers::LinesStream;
+use tokio_stream::{Stream, StreamExt};
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum SttError {
+    #[error("Failed to connect to STT service at '{0}'")]
+    #[diagnostic(
+        code(vetta::stt::connect_failed),
+        help("Make sure the whisper service is running and --socket/WHISPER_SOCK points at it.")
+    )]
+    Connect(String),
+
+    #[error(transparent)]
+    #[diagnostic(code(vetta::stt::io_error))]
+    Io(#[from] std::io::Error),
+
+    #[error("Malformed transcript chunk from STT service: {0}")]
+    #[diagnostic(code(vetta::stt::bad_chunk))]
+    BadChunk(String),
+}
+
+/// Options layered from [`crate::config::PipelineConfig`] (plus any CLI
+/// overrides) that steer a single transcription request.
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscribeOptions {
+    pub language: Option<String>,
+    pub initial_prompt: Option<String>,
+    pub diarization: bool,
+    pub num_speakers: u8,
+}
+
+/// One streamed span of a transcript: text plus the time offsets and, when
+/// `diarization` was requested, the speaker id the STT service attributed it
+/// to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TranscriptChunk {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+    #[serde(default)]
+    pub speaker: u32,
+}
+
+type TranscriptStream = Pin<Box<dyn Stream<Item = Result<TranscriptChunk, SttError>> + Send>>;
+
+/// Abstracts over the transport used to reach the STT service, so the
+/// pipeline can be tested against a fake implementation later.
+pub trait SpeechToText {
+    async fn transcribe(
+        &self,
+        path: &str,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptStream, SttError>;
+}
+
+#[derive(Debug, Serialize)]
+struct TranscribeRequest<'a> {
+    path: &'a str,
+    #[serde(flatten)]
+    options: TranscribeOptions,
+}
+
+/// Talks to a local Whisper-compatible STT service over a Unix domain
+/// socket. Each `transcribe` call opens its own connection, writes a single
+/// JSON request line, then reads newline-delimited JSON [`TranscriptChunk`]s
+/// off the response until the service closes the socket.
+pub struct LocalSttStrategy {
+    socket_path: PathBuf,
+}
+
+impl LocalSttStrategy {
+    /// Connects once up front so a missing/unreachable socket is reported
+    /// immediately, rather than on the first `transcribe` call.
+    pub async fn connect(socket_path: impl AsRef<Path>) -> Result<Self, SttError> {
+        let socket_path = socket_path.as_ref().to_path_buf();
+        UnixStream::connect(&socket_path)
+            .await
+            .map_err(|e| SttError::Connect(format!("{}: {e}", socket_path.display())))?;
+        Ok(Self { socket_path })
+    }
+}
+
+impl SpeechToText for LocalSttStrategy {
+    async fn transcribe(
+        &self,
+        path: &str,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptStream, SttError> {
+        let socket = UnixStream::connect(&self.socket_path)
+            .await
+            .map_err(|e| SttError::Connect(format!("{}: {e}", self.socket_path.display())))?;
+
+        let (read_half, mut write_half) = tokio::io::split(socket);
+
+        let request = TranscribeRequest { path, options };
+        let mut payload =
+            serde_json::to_vec(&request).map_err(|e| SttError::BadChunk(e.to_string()))?;
+        payload.push(b'\n');
+        write_half.write_all(&payload).await?;
+        write_half.shutdown().await?;
+
+        let lines = LinesStream::new(BufReader::new(read_half).lines());
+        let chunks = lines.map(|line| {
+            let line = line?;
+            serde_json::from_str(&line).map_err(|e| SttError::BadChunk(e.to_string()))
+        });
+
+        Ok(Box::pin(chunks))
+    }
+}
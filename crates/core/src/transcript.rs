@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+/// One attributed span of a transcript: a chunk of text with its time
+/// offsets and, when diarization is enabled, a speaker id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Segment {
+    pub start: f64,
+    pub end: f64,
+    pub speaker: Option<u32>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Txt,
+    Srt,
+    Vtt,
+    Json,
+}
+
+/// Renders `segments` in the requested output format.
+pub fn render(segments: &[Segment], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Txt => render_txt(segments),
+        OutputFormat::Srt => render_srt(segments),
+        OutputFormat::Vtt => render_vtt(segments),
+        OutputFormat::Json => render_json(segments),
+    }
+}
+
+fn render_txt(segments: &[Segment]) -> String {
+    let mut out = String::new();
+    for segment in segments {
+        out.push_str(&speaker_prefix(segment));
+        out.push_str(&segment.text);
+        out.push('\n');
+    }
+    out
+}
+
+fn render_srt(segments: &[Segment]) -> String {
+    let mut out = String::new();
+    for (index, segment) in segments.iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}{}\n\n",
+            index + 1,
+            format_timestamp(segment.start, ','),
+            format_timestamp(segment.end, ','),
+            speaker_prefix(segment),
+            segment.text
+        ));
+    }
+    out
+}
+
+fn render_vtt(segments: &[Segment]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for (index, segment) in segments.iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}{}\n\n",
+            index + 1,
+            format_timestamp(segment.start, '.'),
+            format_timestamp(segment.end, '.'),
+            speaker_prefix(segment),
+            segment.text
+        ));
+    }
+    out
+}
+
+fn render_json(segments: &[Segment]) -> String {
+    serde_json::to_string_pretty(segments).unwrap_or_else(|_| "[]".to_string())
+}
+
+fn speaker_prefix(segment: &Segment) -> String {
+    match segment.speaker {
+        Some(speaker) => format!("[Speaker {speaker}] "),
+        None => String::new(),
+    }
+}
+
+/// Formats seconds as `HH:MM:SS{sep}mmm`, the shared shape of SRT (`,`) and
+/// VTT (`.`) cue timestamps.
+fn format_timestamp(seconds: f64, ms_separator: char) -> String {
+    let total_ms = (seconds * 1000.0).round().max(0.0) as i64;
+    let ms = total_ms % 1000;
+    let total_s = total_ms / 1000;
+    let s = total_s % 60;
+    let total_m = total_s / 60;
+    let m = total_m % 60;
+    let h = total_m / 60;
+    format!("{h:02}:{m:02}:{s:02}{ms_separator}{ms:03}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments() -> Vec<Segment> {
+        vec![
+            Segment {
+                start: 0.0,
+                end: 2.5,
+                speaker: Some(1),
+                text: "Good morning everyone.".to_string(),
+            },
+            Segment {
+                start: 2.5,
+                end: 65.125,
+                speaker: Some(2),
+                text: "Thanks for joining the call.".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn txt_includes_speaker_tags_when_present() {
+        let out = render_txt(&segments());
+        assert_eq!(
+            out,
+            "[Speaker 1] Good morning everyone.\n[Speaker 2] Thanks for joining the call.\n"
+        );
+    }
+
+    #[test]
+    fn txt_omits_speaker_tags_without_diarization() {
+        let segments = vec![Segment {
+            start: 0.0,
+            end: 1.0,
+            speaker: None,
+            text: "Hello.".to_string(),
+        }];
+        assert_eq!(render_txt(&segments), "Hello.\n");
+    }
+
+    #[test]
+    fn srt_formats_cues_with_comma_separated_millis() {
+        let out = render_srt(&segments());
+        assert!(out.starts_with("1\n00:00:00,000 --> 00:00:02,500\n[Speaker 1] Good morning everyone.\n\n"));
+        assert!(out.contains("2\n00:00:02,500 --> 00:01:05,125\n[Speaker 2] Thanks for joining the call.\n\n"));
+    }
+
+    #[test]
+    fn vtt_starts_with_header_and_uses_dot_separated_millis() {
+        let out = render_vtt(&segments());
+        assert!(out.starts_with("WEBVTT\n\n1\n00:00:00.000 --> 00:00:02.500\n"));
+    }
+
+    #[test]
+    fn json_round_trips_segment_fields() {
+        let out = render_json(&segments());
+        let parsed: Vec<Segment> = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed, segments());
+    }
+}